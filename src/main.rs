@@ -1,63 +1,173 @@
-use std::net::{IpAddr, Ipv4Addr};
+use std::collections::{HashMap, HashSet};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::num::NonZeroU32;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use clap::Parser;
 use colored::Colorize;
 use futures::future::join_all;
 use governor::{Quota, RateLimiter};
-use hyper::{Client, Uri};
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Client, Request, Response, Server, StatusCode, Uri};
 use hyper_rustls::HttpsConnectorBuilder;
 use local_ipaddress::get;
-use nonzero_ext::nonzero;
+use serde::Serialize;
 use tokio::net::TcpStream;
 use tokio::sync::{Mutex, Semaphore};
 use tokio::time::timeout;
 use regex::Regex;
+use trust_dns_resolver::config::{ResolverConfig, ResolverOpts};
+use trust_dns_resolver::TokioAsyncResolver;
 
 
 #[derive(Parser)]
 struct Cli {
-    /// Subnet size to scan (e.g., 24, 16, 8)
-    #[clap(default_value = "24")]
+    /// Targets to scan: CIDRs (10.0.0.0/24), single IPs, or hostnames.
+    /// When omitted, falls back to scanning the local machine's subnet.
+    targets: Vec<String>,
+
+    /// Subnet size to scan when no targets are given (e.g., 24, 16, 8)
+    #[clap(long, default_value = "24")]
     subnet_size: u8,
+
+    /// Serve live results over SSE at http://<addr>/events (e.g. 127.0.0.1:8080)
+    #[clap(long)]
+    serve: Option<String>,
+
+    /// Ports to scan
+    #[clap(
+        long,
+        value_delimiter = ',',
+        default_value = "80,443,8008,3000,5000,9080,9443,8000,8001,8080,8443,9000,9001"
+    )]
+    ports: Vec<u16>,
+
+    /// Maximum number of in-flight scans at once
+    #[clap(long, default_value_t = 100)]
+    concurrency: usize,
+
+    /// Maximum scans per second
+    #[clap(long, default_value_t = 300)]
+    rate: u32,
+
+    /// ICMP ping timeout in milliseconds
+    #[clap(long, default_value_t = 700)]
+    ping_timeout: u64,
+
+    /// TCP connect timeout in milliseconds
+    #[clap(long, default_value_t = 700)]
+    tcp_timeout: u64,
+
+    /// HTTP request timeout in seconds
+    #[clap(long, default_value_t = 6)]
+    http_timeout: u64,
+
+    /// Output format for results
+    #[clap(long, value_enum, default_value = "text")]
+    output: OutputFormat,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+enum OutputFormat {
+    /// Human-readable, color-coded lines
+    Text,
+    /// One JSON object per `ScanResult`, plus a trailing summary record
+    Json,
 }
 
-const PORTS: &[u16] = &[80, 443, 8008, 3000, 5000, 9080, 9443, 8000, 8001, 8080, 8443, 9000, 9001];
-const CONCURRENT_REQUESTS: usize = 100;
-const RATE_LIMIT: u32 = 300;
-const PING_TIMEOUT: Duration = Duration::from_millis(700);
-const TCP_TIMEOUT: Duration = Duration::from_millis(700);
-const HTTP_TIMEOUT: Duration = Duration::from_secs(6);
+// A resolved host to scan, plus the hostname that produced `ip`, if any.
+#[derive(Debug, Clone)]
+struct ScanTarget {
+    ip: IpAddr,
+    hostname: Option<String>,
+}
+
+// Tuning knobs for a single run, collected from CLI flags.
+struct ScanConfig {
+    ports: Vec<u16>,
+    ping_timeout: Duration,
+    tcp_timeout: Duration,
+    http_timeout: Duration,
+}
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let args = Cli::parse();
-    let local_ip: IpAddr = get().unwrap().parse()?;
 
-    let ip_ranges = calculate_ip_ranges(local_ip, args.subnet_size);
-    let total_ips = ip_ranges.len();
+    let targets = if args.targets.is_empty() {
+        let local_ip: IpAddr = get().unwrap().parse()?;
+        calculate_ip_ranges(local_ip, args.subnet_size)
+            .into_iter()
+            .map(|ip| ScanTarget { ip, hostname: None })
+            .collect()
+    } else {
+        let resolver = TokioAsyncResolver::tokio(ResolverConfig::default(), ResolverOpts::default())?;
+        resolve_targets(&args.targets, &resolver).await
+    };
+    let total_ips = targets.len();
+
+    let config = Arc::new(ScanConfig {
+        ports: args.ports.clone(),
+        ping_timeout: Duration::from_millis(args.ping_timeout),
+        tcp_timeout: Duration::from_millis(args.tcp_timeout),
+        http_timeout: Duration::from_secs(args.http_timeout),
+    });
+
+    let rate = NonZeroU32::new(args.rate).ok_or("--rate must be greater than zero")?;
+    let rate_limiter = Arc::new(RateLimiter::direct(Quota::per_second(rate)));
 
-    let rate_limiter = Arc::new(RateLimiter::direct(Quota::per_second(nonzero!(RATE_LIMIT))));
-    let semaphore = Arc::new(Semaphore::new(CONCURRENT_REQUESTS));
+    if args.concurrency == 0 {
+        return Err("--concurrency must be greater than zero".into());
+    }
+    let semaphore = Arc::new(Semaphore::new(args.concurrency));
 
     let scanned_ips = Arc::new(Mutex::new(0));
 
     // Shared channel for real-time results
     let (tx, mut rx) = tokio::sync::mpsc::channel(100);
 
+    // Broadcast channel fanning results out to any connected SSE clients
+    let sse_tx = Arc::new(tokio::sync::broadcast::channel::<ScanResult>(256).0);
+
+    // Bind eagerly so a taken port fails main() with a clean error instead of
+    // panicking inside a spawned task, and keep the JoinHandle so the process
+    // stays alive serving clients after the scan itself finishes.
+    let serve_handle = if let Some(addr) = &args.serve {
+        let addr: std::net::SocketAddr = addr.parse()?;
+        let sse_tx = Arc::clone(&sse_tx);
+        let make_svc = make_service_fn(move |_conn| {
+            let sse_tx = Arc::clone(&sse_tx);
+            async move {
+                Ok::<_, std::convert::Infallible>(service_fn(move |req| {
+                    handle_sse_request(req, Arc::clone(&sse_tx))
+                }))
+            }
+        });
+        let server = Server::try_bind(&addr)?.serve(make_svc);
+        println!("Streaming live results via SSE at http://{}/events", addr);
+        Some(tokio::spawn(async move {
+            if let Err(e) = server.await {
+                eprintln!("SSE server error: {}", e);
+            }
+        }))
+    } else {
+        None
+    };
+
     // Spawn tasks for scanning
-    let tasks = ip_ranges.into_iter().map(|ip| {
+    let tasks = targets.into_iter().map(|target| {
         let rate_limiter = Arc::clone(&rate_limiter);
         let semaphore = Arc::clone(&semaphore);
         let scanned_ips = Arc::clone(&scanned_ips);
+        let config = Arc::clone(&config);
         let tx = tx.clone();
         tokio::spawn(async move {
             let _permit = semaphore.acquire_owned().await.unwrap();
             rate_limiter.until_ready().await;
 
-            if is_ip_responsive(&ip).await {
-                let scan_results = scan_ip(ip).await;
+            if is_ip_responsive(&target.ip, config.ping_timeout).await {
+                let scan_results = scan_ip(target, &config).await;
                 for result in scan_results {
                     let _ = tx.send(result).await;
                 }
@@ -68,10 +178,43 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         })
     });
 
-    // Spawn a task to print results in real-time
+    // Spawn a task to print results in real-time, fanning each one out to
+    // any connected SSE clients as well, and tallying a final summary
+    let output = args.output;
     let print_handle = tokio::spawn(async move {
+        let mut status_counts: HashMap<u16, usize> = HashMap::new();
+        let mut hosts_seen: HashSet<String> = HashSet::new();
+        let mut open_ports = 0usize;
+
         while let Some(result) = rx.recv().await {
-            println!("{}", result);
+            *status_counts.entry(result.status).or_insert(0) += 1;
+            if let Some(host) = uri_host(&result.ip_port) {
+                hosts_seen.insert(host);
+            }
+            open_ports += 1;
+
+            match output {
+                OutputFormat::Text => println!("{}", result),
+                OutputFormat::Json => println!("{}", serde_json::to_string(&result).unwrap()),
+            }
+            let _ = sse_tx.send(result);
+        }
+
+        let summary = ScanSummary {
+            responsive_hosts: hosts_seen.len(),
+            open_ports,
+            status_counts,
+        };
+
+        match output {
+            OutputFormat::Text => println!(
+                "{} {} responsive hosts, {} open ports, statuses {:?}",
+                "summary:".bold(),
+                summary.responsive_hosts,
+                summary.open_ports,
+                summary.status_counts
+            ),
+            OutputFormat::Json => println!("{}", serde_json::to_string(&summary).unwrap()),
         }
     });
 
@@ -84,41 +227,122 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Wait for the print task to finish
     print_handle.await?;
 
+    // Keep running to serve the SSE dashboard after the scan itself is done
+    if let Some(handle) = serve_handle {
+        handle.await?;
+    }
+
     Ok(())
 }
 
-async fn is_ip_responsive(ip: &IpAddr) -> bool {
+async fn is_ip_responsive(ip: &IpAddr, ping_timeout: Duration) -> bool {
     let payload = [0; 8];
-    match timeout(PING_TIMEOUT, surge_ping::ping(*ip, &payload)).await {
+    match timeout(ping_timeout, surge_ping::ping(*ip, &payload)).await {
         Ok(Ok(_)) => true,
         _ => false,
     }
 }
 
+fn uri_host(ip_port: &str) -> Option<String> {
+    ip_port.parse::<Uri>().ok()?.host().map(|h| h.to_string())
+}
+
+// Final record emitted once scanning completes.
+#[derive(Debug, Serialize)]
+struct ScanSummary {
+    responsive_hosts: usize,
+    open_ports: usize,
+    status_counts: HashMap<u16, usize>,
+}
+
 fn calculate_ip_ranges(base_ip: IpAddr, subnet_size: u8) -> Vec<IpAddr> {
-    if let IpAddr::V4(ipv4) = base_ip {
-        let host_bits = 32 - subnet_size;
-        let network = u32::from(ipv4) & !((1 << host_bits) - 1);
-        let broadcast = network | ((1 << host_bits) - 1);
+    match base_ip {
+        IpAddr::V4(ipv4) => {
+            let host_bits = 32 - subnet_size;
+            // /0 has 32 host bits, which overflows a u32 shift; checked_shl
+            // catches that and falls back to an all-ones mask.
+            let mask = 1u32.checked_shl(host_bits as u32).map_or(u32::MAX, |v| v - 1);
+            let network = u32::from(ipv4) & !mask;
+            let broadcast = network | mask;
+
+            (network..=broadcast)
+                .map(|ip| IpAddr::V4(Ipv4Addr::from(ip)))
+                .collect()
+        }
+        IpAddr::V6(ipv6) => {
+            let host_bits = 128 - subnet_size as u32;
+            let mask = 1u128.checked_shl(host_bits).map_or(u128::MAX, |v| v - 1);
+            let network = u128::from(ipv6) & !mask;
+            let broadcast = network | mask;
+
+            (network..=broadcast)
+                .map(|ip| IpAddr::V6(Ipv6Addr::from(ip)))
+                .collect()
+        }
+    }
+}
 
-        (network..=broadcast)
-            .map(|ip| IpAddr::V4(Ipv4Addr::from(ip)))
-            .collect()
-    } else {
-        vec![]
+// Expand CLI target strings into ScanTargets: CIDRs, bare IPs, or hostnames.
+async fn resolve_targets(inputs: &[String], resolver: &TokioAsyncResolver) -> Vec<ScanTarget> {
+    let mut targets = Vec::new();
+
+    for input in inputs {
+        if let Some((addr, prefix)) = input.split_once('/') {
+            if let (Ok(ip), Ok(prefix)) = (addr.parse::<IpAddr>(), prefix.parse::<u8>()) {
+                let max_prefix = if ip.is_ipv4() { 32 } else { 128 };
+                if prefix > max_prefix {
+                    eprintln!("{} {}", "invalid CIDR prefix:".red(), input);
+                    continue;
+                }
+                targets.extend(
+                    calculate_ip_ranges(ip, prefix)
+                        .into_iter()
+                        .map(|ip| ScanTarget { ip, hostname: None }),
+                );
+                continue;
+            }
+        }
+
+        if let Ok(ip) = input.parse::<IpAddr>() {
+            targets.push(ScanTarget { ip, hostname: None });
+            continue;
+        }
+
+        match resolver.lookup_ip(input.as_str()).await {
+            Ok(lookup) => {
+                if let Some(ip) = lookup.iter().next() {
+                    targets.push(ScanTarget {
+                        ip,
+                        hostname: Some(input.clone()),
+                    });
+                }
+            }
+            Err(_) => eprintln!("{} {}", "could not resolve".red(), input),
+        }
     }
+
+    targets
 }
 
-async fn scan_ip(ip: IpAddr) -> Vec<ScanResult> {
+async fn scan_ip(target: ScanTarget, config: &ScanConfig) -> Vec<ScanResult> {
     let mut results = Vec::new();
-    let scan_tasks = PORTS.iter().map(|&port| {
+    let ip = target.ip;
+    let tcp_timeout = config.tcp_timeout;
+    let http_timeout = config.http_timeout;
+    let scan_tasks = config.ports.iter().map(|&port| {
         let ip = ip;
+        let hostname = target.hostname.clone();
         tokio::spawn(async move {
-            if let Ok(Ok(_)) = timeout(TCP_TIMEOUT, TcpStream::connect((ip, port))).await {
+            let connect_start = Instant::now();
+            if let Ok(Ok(_)) = timeout(tcp_timeout, TcpStream::connect((ip, port))).await {
+                let connect_ms = connect_start.elapsed().as_millis() as u64;
                 for scheme in &["http", "https"] {
                     let url = format!("{}://{}:{}", scheme, ip, port);
-                    match check_web_server(&url).await {
-                        Ok(result) => return Some(result),
+                    match check_web_server(&url, connect_ms, http_timeout).await {
+                        Ok(mut result) => {
+                            result.hostname = hostname.clone();
+                            return Some(result);
+                        }
                         Err(_) => {} // Suppress error messages
                     }
                 }
@@ -136,28 +360,101 @@ async fn scan_ip(ip: IpAddr) -> Vec<ScanResult> {
     results
 }
 
-async fn check_web_server(url: &str) -> Result<ScanResult, Box<dyn std::error::Error>> {
+async fn check_web_server(
+    url: &str,
+    connect_ms: u64,
+    http_timeout: Duration,
+) -> Result<ScanResult, Box<dyn std::error::Error>> {
+    // Let ALPN negotiate http1 vs http2
     let https = HttpsConnectorBuilder::new()
         .with_native_roots()
         .https_or_http()
         .enable_http1()
+        .enable_http2()
         .build();
 
     let client: Client<_, hyper::Body> = Client::builder().build(https);
     let uri: Uri = url.parse()?;
 
-    let response = timeout(HTTP_TIMEOUT, client.get(uri.clone())).await??;
+    let request_start = Instant::now();
+    let response = timeout(http_timeout, client.get(uri.clone())).await??;
+    let ttfb_ms = request_start.elapsed().as_millis() as u64;
     let status = response.status().as_u16();
+    let http_version = version_str(response.version());
     let body = hyper::body::to_bytes(response.into_body()).await?;
+    let total_ms = request_start.elapsed().as_millis() as u64;
     let title = extract_title(&body);
 
     Ok(ScanResult {
         ip_port: uri.to_string(),
         status,
         title,
+        connect_ms,
+        ttfb_ms,
+        total_ms,
+        hostname: None,
+        http_version,
     })
 }
 
+fn version_str(version: hyper::Version) -> &'static str {
+    match version {
+        hyper::Version::HTTP_09 => "HTTP/0.9",
+        hyper::Version::HTTP_10 => "HTTP/1.0",
+        hyper::Version::HTTP_11 => "HTTP/1.1",
+        hyper::Version::HTTP_2 => "HTTP/2",
+        hyper::Version::HTTP_3 => "HTTP/3",
+        _ => "unknown",
+    }
+}
+
+// Serves /events as an SSE stream; any other path gets a plain 404.
+async fn handle_sse_request(
+    req: Request<Body>,
+    sse_tx: Arc<tokio::sync::broadcast::Sender<ScanResult>>,
+) -> Result<Response<Body>, std::convert::Infallible> {
+    if req.uri().path() != "/events" {
+        return Ok(Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .body(Body::empty())
+            .unwrap());
+    }
+
+    let stream = futures::stream::unfold(sse_tx.subscribe(), |mut rx| async move {
+        loop {
+            match rx.recv().await {
+                Ok(result) => return Some((Ok::<_, std::convert::Infallible>(sse_frame(&result)), rx)),
+                Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(tokio::sync::broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    });
+
+    Ok(Response::builder()
+        .header("Content-Type", "text/event-stream")
+        .header("Cache-Control", "no-cache")
+        .body(Body::wrap_stream(stream))
+        .unwrap())
+}
+
+fn sse_frame(result: &ScanResult) -> String {
+    let ip_port = match &result.hostname {
+        Some(hostname) => format!("{} ({})", result.ip_port, hostname),
+        None => result.ip_port.clone(),
+    };
+
+    format!(
+        "data: [{}] {} -> {} {} (connect {}ms, ttfb {}ms, total {}ms)\n\n",
+        result.status,
+        result.title.trim(),
+        ip_port,
+        result.http_version,
+        result.connect_ms,
+        result.ttfb_ms,
+        result.total_ms
+    )
+}
+
 fn extract_title(body: &[u8]) -> String {
     let body_str = String::from_utf8_lossy(body);
     
@@ -171,11 +468,16 @@ fn extract_title(body: &[u8]) -> String {
     "No Title Found".to_string()
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize)]
 struct ScanResult {
     ip_port: String,
     status: u16,
     title: String,
+    connect_ms: u64,
+    ttfb_ms: u64,
+    total_ms: u64,
+    hostname: Option<String>,
+    http_version: &'static str,
 }
 
 impl std::fmt::Display for ScanResult {
@@ -188,13 +490,30 @@ impl std::fmt::Display for ScanResult {
             _ => "white",
         };
 
+        let latency_color = |ms: u64| match ms {
+            0..=100 => "green",
+            101..=400 => "yellow",
+            _ => "red",
+        };
+
+        let ip_port = match &self.hostname {
+            Some(hostname) => format!("{} ({})", self.ip_port, hostname),
+            None => self.ip_port.clone(),
+        };
+
         write!(
             f,
-            "{} {} {} {}",
+            "{} {} {} {} {} {}",
             format!("[{}]", self.status).color(status_color).bold(),
             self.title.trim().bright_magenta(),
             "->".bright_black(),
-            self.ip_port.bright_cyan()
+            ip_port.bright_cyan(),
+            self.http_version.bright_black(),
+            format!(
+                "(connect {}ms, ttfb {}ms, total {}ms)",
+                self.connect_ms, self.ttfb_ms, self.total_ms
+            )
+            .color(latency_color(self.total_ms))
         )
     }
 }